@@ -27,11 +27,14 @@ use crate::{convert_box_required, convert_required};
 
 use arrow::datatypes::{DataType, Schema};
 use datafusion::execution::context::{ExecutionConfig, ExecutionContextState};
+use datafusion::execution::runtime_env::RuntimeEnv;
 use datafusion::logical_plan::{DFSchema, Expr};
 use datafusion::physical_plan::expressions::col;
 use datafusion::physical_plan::planner::DefaultPhysicalPlanner;
 use datafusion::physical_plan::{
+    avro::AvroExec,
     coalesce_batches::CoalesceBatchesExec,
+    cross_join::CrossJoinExec,
     csv::CsvExec,
     empty::EmptyExec,
     expressions::{Avg, Column, PhysicalSortExpr},
@@ -41,10 +44,11 @@ use datafusion::physical_plan::{
     limit::{GlobalLimitExec, LocalLimitExec},
     parquet::ParquetExec,
     projection::ProjectionExec,
+    repartition::RepartitionExec,
     sort::{SortExec, SortOptions},
 };
 
-use datafusion::physical_plan::{AggregateExpr, ExecutionPlan, PhysicalExpr};
+use datafusion::physical_plan::{AggregateExpr, ExecutionPlan, Partitioning, PhysicalExpr};
 use datafusion::prelude::CsvReadOptions;
 
 use protobuf::logical_expr_node::ExprType;
@@ -52,216 +56,451 @@ use protobuf::physical_plan_node::PhysicalPlanType;
 
 use datafusion::physical_plan::hash_aggregate::{AggregateMode, HashAggregateExec};
 
+/// A codec that allows custom `ExecutionPlan` implementations (e.g. bespoke data sources or
+/// specialized joins) that are not part of the built-in `PhysicalPlanType` enum to be
+/// deserialized as an opaque `PhysicalPlanType::Extension`.
+pub trait PhysicalExtensionCodec: Send + Sync {
+    fn try_decode(
+        &self,
+        buf: &[u8],
+        inputs: &[Arc<dyn ExecutionPlan>],
+    ) -> Result<Arc<dyn ExecutionPlan>, BallistaError>;
+}
+
+/// Default codec used when the caller does not register one of their own. It rejects every
+/// extension node, which preserves today's behavior of failing to deserialize plans outside
+/// the built-in enum.
+#[derive(Debug, Default)]
+pub struct DefaultPhysicalExtensionCodec {}
+
+impl PhysicalExtensionCodec for DefaultPhysicalExtensionCodec {
+    fn try_decode(
+        &self,
+        _buf: &[u8],
+        _inputs: &[Arc<dyn ExecutionPlan>],
+    ) -> Result<Arc<dyn ExecutionPlan>, BallistaError> {
+        Err(BallistaError::General(
+            "No PhysicalExtensionCodec was registered to decode this physical plan extension"
+                .to_string(),
+        ))
+    }
+}
+
 impl TryInto<Arc<dyn ExecutionPlan>> for &protobuf::PhysicalPlanNode {
     type Error = BallistaError;
 
     fn try_into(self) -> Result<Arc<dyn ExecutionPlan>, Self::Error> {
-        let plan = self.physical_plan_type.as_ref().ok_or_else(|| {
-            proto_error(format!(
-                "physical_plan::from_proto() Unsupported physical plan '{:?}'",
-                self
-            ))
-        })?;
-        match plan {
-            PhysicalPlanType::Projection(projection) => {
-                let input: Arc<dyn ExecutionPlan> = convert_box_required!(projection.input)?;
-                let exprs = projection
-                    .expr
-                    .iter()
-                    .map(|expr| {
-                        compile_expr(expr, &input.schema()).map(|e| (e, "unused".to_string()))
-                    })
-                    // .map(|expr| expr.try_into().map(|e| (e, "unused".to_string())))
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Arc::new(ProjectionExec::try_new(exprs, input)?))
-            }
-            PhysicalPlanType::Filter(filter) => {
-                let input: Arc<dyn ExecutionPlan> = convert_box_required!(filter.input)?;
-                let predicate = compile_expr(filter.expr.as_ref().unwrap(), &input.schema())?;
-                Ok(Arc::new(FilterExec::try_new(predicate, input)?))
-            }
-            PhysicalPlanType::CsvScan(scan) => {
-                let schema = Arc::new(convert_required!(scan.schema)?);
-                let options = CsvReadOptions::new()
-                    .has_header(scan.has_header)
-                    .file_extension(&scan.file_extension)
-                    .delimiter(scan.delimiter.as_bytes()[0])
-                    .schema(&schema);
-                // TODO we don't care what the DataFusion batch size was because Ballista will
-                // have its own configs. Hard-code for now.
-                let batch_size = 32768;
-                let projection = scan.projection.iter().map(|i| *i as usize).collect();
-                Ok(Arc::new(CsvExec::try_new(
-                    &scan.path,
-                    options,
-                    Some(projection),
-                    batch_size,
-                )?))
-            }
-            PhysicalPlanType::ParquetScan(scan) => {
-                let projection = scan.projection.iter().map(|i| *i as usize).collect();
-                // TODO we don't care what the DataFusion batch size was because Ballista will
-                // have its own configs. Hard-code for now.
-                let batch_size = 32768;
-                let max_concurrency = 8;
-                let filenames: Vec<&str> = scan.filename.iter().map(|s| s.as_str()).collect();
-                Ok(Arc::new(ParquetExec::try_from_files(
-                    &filenames,
-                    Some(projection),
-                    None,
-                    batch_size,
-                    max_concurrency,
-                )?))
-            }
-            PhysicalPlanType::Selection(_) => unimplemented!(),
-            PhysicalPlanType::CoalesceBatches(coalesce_batches) => {
-                let input: Arc<dyn ExecutionPlan> = convert_box_required!(coalesce_batches.input)?;
-                Ok(Arc::new(CoalesceBatchesExec::new(
+        parse_physical_plan(
+            self,
+            &Arc::new(RuntimeEnv::default()),
+            &DefaultPhysicalExtensionCodec::default(),
+        )
+        .map(|(plan, _unbounded)| plan)
+    }
+}
+
+fn parse_required_physical_box(
+    input: &Option<Box<protobuf::PhysicalPlanNode>>,
+    runtime: &Arc<RuntimeEnv>,
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<(Arc<dyn ExecutionPlan>, bool), BallistaError> {
+    input
+        .as_ref()
+        .map(|node| parse_physical_plan(node, runtime, extension_codec))
+        .transpose()?
+        .ok_or_else(|| proto_error("physical_plan::from_proto() Missing required input plan"))
+}
+
+/// Converts a `protobuf::PhysicalPlanNode` into an `Arc<dyn ExecutionPlan>`, using
+/// `extension_codec` to reconstruct any `PhysicalPlanType::Extension` nodes that fall outside
+/// the built-in plan types. Also returns whether the reconstructed plan's output is unbounded
+/// (it is, or streams from, an unbounded source), so that blocking operators further up the
+/// tree can reject plan shapes that would never terminate.
+pub fn parse_physical_plan(
+    plan_node: &protobuf::PhysicalPlanNode,
+    runtime: &Arc<RuntimeEnv>,
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<(Arc<dyn ExecutionPlan>, bool), BallistaError> {
+    let plan = plan_node.physical_plan_type.as_ref().ok_or_else(|| {
+        proto_error(format!(
+            "physical_plan::from_proto() Unsupported physical plan '{:?}'",
+            plan_node
+        ))
+    })?;
+    match plan {
+        PhysicalPlanType::Projection(projection) => {
+            let (input, unbounded) =
+                parse_required_physical_box(&projection.input, runtime, extension_codec)?;
+            let exprs = projection
+                .expr
+                .iter()
+                .map(|expr| {
+                    compile_expr(expr, &input.schema()).map(|e| (e, "unused".to_string()))
+                })
+                // .map(|expr| expr.try_into().map(|e| (e, "unused".to_string())))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((Arc::new(ProjectionExec::try_new(exprs, input)?), unbounded))
+        }
+        PhysicalPlanType::Filter(filter) => {
+            let (input, unbounded) =
+                parse_required_physical_box(&filter.input, runtime, extension_codec)?;
+            let predicate = compile_expr(filter.expr.as_ref().unwrap(), &input.schema())?;
+            Ok((Arc::new(FilterExec::try_new(predicate, input)?), unbounded))
+        }
+        PhysicalPlanType::Scan(scan) => {
+            // Every file-based scan shares the same shape: a list of file paths, a
+            // projection, a target partition count, and a batch size handed down by the
+            // scheduler, with only the format-specific options varying.
+            let projection = scan.projection.iter().map(|i| *i as usize).collect::<Vec<_>>();
+            let batch_size = scan.batch_size as usize;
+            let target_partitions = scan.target_partitions as usize;
+            let filenames: Vec<&str> = scan.path.iter().map(|s| s.as_str()).collect();
+            // An unbounded source (e.g. a FIFO or tailed file) never produces a final batch, so
+            // every blocking operator built on top of this scan must check this flag before
+            // reconstructing itself.
+            let unbounded = scan.infinite_source;
+            let file_format = scan.file_format.as_ref().ok_or_else(|| {
+                proto_error(format!(
+                    "physical_plan::from_proto() Missing file_format in FileScanExecConfig {:?}",
+                    plan_node
+                ))
+            })?;
+            let plan: Arc<dyn ExecutionPlan> = match file_format {
+                protobuf::file_scan_exec_config::FileFormat::Csv(csv) => {
+                    let schema = Arc::new(convert_required!(scan.schema)?);
+                    let options = CsvReadOptions::new()
+                        .has_header(csv.has_header)
+                        .file_extension(&csv.file_extension)
+                        .delimiter(csv.delimiter.as_bytes()[0])
+                        .schema(&schema);
+                    // Unlike Parquet/Avro, CsvExec only scans a single root path (it walks
+                    // that path itself when it names a directory), so a list of anything
+                    // other than exactly one path is a scheduler bug, not something to
+                    // silently truncate or panic on.
+                    let path = match filenames.as_slice() {
+                        [path] => *path,
+                        [] => {
+                            return Err(proto_error(
+                                "physical_plan::from_proto() CSV scan requires exactly one path, got none",
+                            ))
+                        }
+                        _ => {
+                            return Err(proto_error(format!(
+                                "physical_plan::from_proto() CSV scan requires exactly one path, got {}",
+                                filenames.len()
+                            )))
+                        }
+                    };
+                    Arc::new(CsvExec::try_new(
+                        path,
+                        options,
+                        Some(projection),
+                        batch_size,
+                    )?)
+                }
+                protobuf::file_scan_exec_config::FileFormat::Parquet(_) => {
+                    Arc::new(ParquetExec::try_from_files(
+                        &filenames,
+                        Some(projection),
+                        None,
+                        batch_size,
+                        target_partitions,
+                    )?)
+                }
+                protobuf::file_scan_exec_config::FileFormat::Avro(_) => {
+                    Arc::new(AvroExec::try_from_files(
+                        &filenames,
+                        Some(projection),
+                        None,
+                        batch_size,
+                        target_partitions,
+                    )?)
+                }
+            };
+            Ok((plan, unbounded))
+        }
+        PhysicalPlanType::Selection(_) => unimplemented!(),
+        PhysicalPlanType::CoalesceBatches(coalesce_batches) => {
+            let (input, unbounded) =
+                parse_required_physical_box(&coalesce_batches.input, runtime, extension_codec)?;
+            Ok((
+                Arc::new(CoalesceBatchesExec::new(
                     input,
                     coalesce_batches.target_batch_size as usize,
-                )))
-            }
-            PhysicalPlanType::GlobalLimit(limit) => {
-                let input: Arc<dyn ExecutionPlan> = convert_box_required!(limit.input)?;
-                Ok(Arc::new(GlobalLimitExec::new(
-                    input,
-                    limit.limit as usize,
-                    0,
-                )))
-                // TODO: concurrency param doesn't seem to be used in datafusion. not sure how to fill this in
-            }
-            PhysicalPlanType::LocalLimit(limit) => {
-                let input: Arc<dyn ExecutionPlan> = convert_box_required!(limit.input)?;
-                Ok(Arc::new(LocalLimitExec::new(input, limit.limit as usize)))
+                )),
+                unbounded,
+            ))
+        }
+        PhysicalPlanType::GlobalLimit(limit) => {
+            let (input, _unbounded) =
+                parse_required_physical_box(&limit.input, runtime, extension_codec)?;
+            // A limit stops pulling from its input once `limit` rows have been produced, so
+            // its output is bounded even when the input is not.
+            Ok((
+                Arc::new(GlobalLimitExec::new(input, limit.limit as usize, 0)),
+                false,
+            ))
+            // TODO: concurrency param doesn't seem to be used in datafusion. not sure how to fill this in
+        }
+        PhysicalPlanType::LocalLimit(limit) => {
+            let (input, _unbounded) =
+                parse_required_physical_box(&limit.input, runtime, extension_codec)?;
+            // Same reasoning as GlobalLimit: the limit itself terminates the stream.
+            Ok((
+                Arc::new(LocalLimitExec::new(input, limit.limit as usize)),
+                false,
+            ))
+        }
+        PhysicalPlanType::HashAggregate(hash_agg) => {
+            let (input, unbounded) =
+                parse_required_physical_box(&hash_agg.input, runtime, extension_codec)?;
+            let mode = protobuf::AggregateMode::from_i32(hash_agg.mode).ok_or_else(|| {
+                proto_error(format!(
+                    "Received a HashAggregateNode message with unknown AggregateMode {}",
+                    hash_agg.mode
+                ))
+            })?;
+            let agg_mode: AggregateMode = match mode {
+                protobuf::AggregateMode::Partial => AggregateMode::Partial,
+                protobuf::AggregateMode::Final => AggregateMode::Final,
+            };
+            let group = hash_agg
+                .group_expr
+                .iter()
+                .map(|expr| {
+                    compile_expr(expr, &input.schema()).map(|e| (e, "unused".to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let datafusion_planner = DefaultPhysicalPlanner::default();
+            let ctx_state = ExecutionContextState {
+                datasources: Default::default(),
+                scalar_functions: Default::default(),
+                var_provider: Default::default(),
+                aggregate_functions: Default::default(),
+                config: ExecutionConfig::new(),
+            };
+
+            let agg_expr = hash_agg
+                .aggr_expr
+                .iter()
+                .map(|expr| {
+                    let expr2: Expr = expr.try_into().unwrap();
+                    let logical_schema: DFSchema =
+                        input.schema().as_ref().clone().try_into().unwrap();
+                    datafusion_planner.create_aggregate_expr(
+                        &expr2,
+                        &logical_schema,
+                        input.schema().as_ref(),
+                        &ctx_state,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // A final hash aggregate, grouped or not, has to consume its entire input before
+            // it can emit any output batch (it is finalizing partial accumulators, not
+            // streaming them), so it can never be built on top of an unbounded source. Only
+            // `Partial` mode may sit directly on an unbounded input, since it forwards partial
+            // accumulators downstream as they arrive.
+            if matches!(agg_mode, AggregateMode::Final) && unbounded {
+                return Err(BallistaError::General(
+                    "physical_plan::from_proto() a final hash aggregate cannot be built on an \
+                     unbounded input"
+                        .to_string(),
+                ));
             }
-            PhysicalPlanType::HashAggregate(hash_agg) => {
-                let input: Arc<dyn ExecutionPlan> = convert_box_required!(hash_agg.input)?;
-                let mode = protobuf::AggregateMode::from_i32(hash_agg.mode).ok_or_else(|| {
+
+            Ok((
+                Arc::new(HashAggregateExec::try_new(
+                    agg_mode, group, agg_expr, input,
+                )?),
+                unbounded,
+            ))
+        }
+        PhysicalPlanType::HashJoin(hashjoin) => {
+            let (left, left_unbounded) =
+                parse_required_physical_box(&hashjoin.left, runtime, extension_codec)?;
+            let (right, right_unbounded) =
+                parse_required_physical_box(&hashjoin.right, runtime, extension_codec)?;
+            let on: Vec<(String, String)> = hashjoin
+                .on
+                .iter()
+                .map(|col| (col.left.clone(), col.right.clone()))
+                .collect();
+            let join_type =
+                protobuf::JoinType::from_i32(hashjoin.join_type).ok_or_else(|| {
                     proto_error(format!(
-                        "Received a HashAggregateNode message with unknown AggregateMode {}",
-                        hash_agg.mode
+                        "Received a HashJoinNode message with unknown JoinType {}",
+                        hashjoin.join_type
                     ))
                 })?;
-                let agg_mode: AggregateMode = match mode {
-                    protobuf::AggregateMode::Partial => AggregateMode::Partial,
-                    protobuf::AggregateMode::Final => AggregateMode::Final,
-                };
-                let group = hash_agg
-                    .group_expr
-                    .iter()
-                    .map(|expr| {
-                        compile_expr(expr, &input.schema()).map(|e| (e, "unused".to_string()))
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let datafusion_planner = DefaultPhysicalPlanner::default();
-                let ctx_state = ExecutionContextState {
-                    datasources: Default::default(),
-                    scalar_functions: Default::default(),
-                    var_provider: Default::default(),
-                    aggregate_functions: Default::default(),
-                    config: ExecutionConfig::new(),
-                };
-
-                let agg_expr = hash_agg
-                    .aggr_expr
-                    .iter()
-                    .map(|expr| {
-                        let expr2: Expr = expr.try_into().unwrap();
-                        let logical_schema: DFSchema =
-                            input.schema().as_ref().clone().try_into().unwrap();
-                        datafusion_planner.create_aggregate_expr(
-                            &expr2,
-                            &logical_schema,
-                            input.schema().as_ref(),
-                            &ctx_state,
-                        )
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                Ok(Arc::new(HashAggregateExec::try_new(
-                    agg_mode, group, agg_expr, input,
-                )?))
+            let join_type = match join_type {
+                protobuf::JoinType::Inner => JoinType::Inner,
+                protobuf::JoinType::Left => JoinType::Left,
+                protobuf::JoinType::Right => JoinType::Right,
+                protobuf::JoinType::Full => JoinType::Full,
+                protobuf::JoinType::Leftsemi => JoinType::LeftSemi,
+                protobuf::JoinType::Rightsemi => JoinType::RightSemi,
+                protobuf::JoinType::Leftanti => JoinType::LeftAnti,
+                protobuf::JoinType::Rightanti => JoinType::RightAnti,
+            };
+            // Only the streaming (probe) side of a hash join may be unbounded; the build side
+            // has to be materialized into the hash table before any probing can start.
+            if left_unbounded {
+                return Err(BallistaError::General(
+                    "physical_plan::from_proto() the build side of a hash join cannot be \
+                     unbounded"
+                        .to_string(),
+                ));
             }
-            PhysicalPlanType::HashJoin(hashjoin) => {
-                let left: Arc<dyn ExecutionPlan> = convert_box_required!(hashjoin.left)?;
-                let right: Arc<dyn ExecutionPlan> = convert_box_required!(hashjoin.right)?;
-                let on: Vec<(String, String)> = hashjoin
-                    .on
-                    .iter()
-                    .map(|col| (col.left.clone(), col.right.clone()))
-                    .collect();
-                let join_type =
-                    protobuf::JoinType::from_i32(hashjoin.join_type).ok_or_else(|| {
+            Ok((
+                Arc::new(HashJoinExec::try_new(left, right, &on, &join_type)?),
+                right_unbounded,
+            ))
+        }
+        PhysicalPlanType::CrossJoin(crossjoin) => {
+            let (left, left_unbounded) =
+                parse_required_physical_box(&crossjoin.left, runtime, extension_codec)?;
+            let (right, right_unbounded) =
+                parse_required_physical_box(&crossjoin.right, runtime, extension_codec)?;
+            // Like a hash join's build side, the left input is materialized into memory
+            // before the right side is streamed against it, so it cannot be unbounded.
+            if left_unbounded {
+                return Err(BallistaError::General(
+                    "physical_plan::from_proto() the build side of a cross join cannot be \
+                     unbounded"
+                        .to_string(),
+                ));
+            }
+            Ok((Arc::new(CrossJoinExec::try_new(left, right)?), right_unbounded))
+        }
+        PhysicalPlanType::ShuffleReader(shuffle_reader) => {
+            let schema = Arc::new(convert_required!(shuffle_reader.schema)?);
+            let partition_location: Vec<PartitionLocation> = shuffle_reader
+                .partition_location
+                .iter()
+                .map(|p| p.clone().try_into())
+                .collect::<Result<Vec<_>, BallistaError>>()?;
+            let shuffle_reader = ShuffleReaderExec::try_new(partition_location, schema)?;
+            Ok((Arc::new(shuffle_reader), false))
+        }
+        PhysicalPlanType::Empty(empty) => {
+            let schema = Arc::new(convert_required!(empty.schema)?);
+            Ok((Arc::new(EmptyExec::new(empty.produce_one_row, schema)), false))
+        }
+        PhysicalPlanType::Sort(sort) => {
+            let (input, unbounded) =
+                parse_required_physical_box(&sort.input, runtime, extension_codec)?;
+            // A global sort has to see every row before it can emit the first one, so it can
+            // never be built on top of an unbounded input.
+            if unbounded {
+                return Err(BallistaError::General(
+                    "physical_plan::from_proto() a sort cannot be built on an unbounded input"
+                        .to_string(),
+                ));
+            }
+            let exprs = sort
+                .expr
+                .iter()
+                .map(|expr| {
+                    let expr = expr.expr_type.as_ref().ok_or_else(|| {
                         proto_error(format!(
-                            "Received a HashJoinNode message with unknown JoinType {}",
-                            hashjoin.join_type
+                            "physical_plan::from_proto() Unexpected expr {:?}",
+                            plan_node
                         ))
                     })?;
-                let join_type = match join_type {
-                    protobuf::JoinType::Inner => JoinType::Inner,
-                    protobuf::JoinType::Left => JoinType::Left,
-                    protobuf::JoinType::Right => JoinType::Right,
-                };
-                Ok(Arc::new(HashJoinExec::try_new(
-                    left, right, &on, &join_type,
-                )?))
-            }
-            PhysicalPlanType::ShuffleReader(shuffle_reader) => {
-                let schema = Arc::new(convert_required!(shuffle_reader.schema)?);
-                let partition_location: Vec<PartitionLocation> = shuffle_reader
-                    .partition_location
-                    .iter()
-                    .map(|p| p.clone().try_into())
-                    .collect::<Result<Vec<_>, BallistaError>>()?;
-                let shuffle_reader = ShuffleReaderExec::try_new(partition_location, schema)?;
-                Ok(Arc::new(shuffle_reader))
-            }
-            PhysicalPlanType::Empty(empty) => {
-                let schema = Arc::new(convert_required!(empty.schema)?);
-                Ok(Arc::new(EmptyExec::new(empty.produce_one_row, schema)))
-            }
-            PhysicalPlanType::Sort(sort) => {
-                let input: Arc<dyn ExecutionPlan> = convert_box_required!(sort.input)?;
-                let exprs = sort
-                    .expr
-                    .iter()
-                    .map(|expr| {
-                        let expr = expr.expr_type.as_ref().ok_or_else(|| {
-                            proto_error(format!(
-                                "physical_plan::from_proto() Unexpected expr {:?}",
-                                self
-                            ))
-                        })?;
-                        if let protobuf::logical_expr_node::ExprType::Sort(sort_expr) = expr {
-                            let expr = sort_expr
-                                .expr
-                                .as_ref()
-                                .ok_or_else(|| {
-                                    proto_error(format!(
-                                        "physical_plan::from_proto() Unexpected sort expr {:?}",
-                                        self
-                                    ))
-                                })?
-                                .as_ref();
-                            Ok(PhysicalSortExpr {
-                                expr: compile_expr(expr, &input.schema())?,
-                                options: SortOptions {
-                                    descending: !sort_expr.asc,
-                                    nulls_first: sort_expr.nulls_first,
-                                },
-                            })
-                        } else {
-                            Err(BallistaError::General(format!(
-                                "physical_plan::from_proto() {:?}",
-                                self
-                            )))
-                        }
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-                // Update concurrency here in the future
-                Ok(Arc::new(SortExec::try_new(exprs, input, 1)?))
-            }
+                    if let protobuf::logical_expr_node::ExprType::Sort(sort_expr) = expr {
+                        let expr = sort_expr
+                            .expr
+                            .as_ref()
+                            .ok_or_else(|| {
+                                proto_error(format!(
+                                    "physical_plan::from_proto() Unexpected sort expr {:?}",
+                                    plan_node
+                                ))
+                            })?
+                            .as_ref();
+                        Ok(PhysicalSortExpr {
+                            expr: compile_expr(expr, &input.schema())?,
+                            options: SortOptions {
+                                descending: !sort_expr.asc,
+                                nulls_first: sort_expr.nulls_first,
+                            },
+                        })
+                    } else {
+                        Err(BallistaError::General(format!(
+                            "physical_plan::from_proto() {:?}",
+                            plan_node
+                        )))
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            // A memory_limit of 0 means the scheduler did not tune this stage; fall back to
+            // the runtime's default pool so the operator still registers with the memory
+            // manager and can spill through the shared disk manager when needed.
+            let memory_limit = if sort.memory_limit > 0 {
+                Some(sort.memory_limit as usize)
+            } else {
+                None
+            };
+            let spill_dir = if sort.spill_dir.is_empty() {
+                runtime.disk_manager.create_tmp_directory()?
+            } else {
+                sort.spill_dir.clone()
+            };
+            Ok((
+                Arc::new(SortExec::try_new_with_runtime(
+                    exprs,
+                    input,
+                    memory_limit,
+                    spill_dir,
+                    runtime.clone(),
+                )?),
+                false,
+            ))
+        }
+        PhysicalPlanType::Repartition(repartition) => {
+            let (input, unbounded) =
+                parse_required_physical_box(&repartition.input, runtime, extension_codec)?;
+            let partitioning_scheme = repartition.partition_method.as_ref().ok_or_else(|| {
+                proto_error(format!(
+                    "physical_plan::from_proto() Unexpected empty partition method in RepartitionNode {:?}",
+                    plan_node
+                ))
+            })?;
+            let partitioning = match partitioning_scheme {
+                protobuf::repartition_node::PartitionMethod::RoundRobin(n) => {
+                    Partitioning::RoundRobinBatch(*n as usize)
+                }
+                protobuf::repartition_node::PartitionMethod::Hash(hash_part) => {
+                    let exprs = hash_part
+                        .hash_expr
+                        .iter()
+                        .map(|expr| compile_expr(expr, &input.schema()))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Partitioning::HashPartitioning(exprs, hash_part.partition_count as usize)
+                }
+            };
+            Ok((
+                Arc::new(RepartitionExec::try_new(input, partitioning)?),
+                unbounded,
+            ))
+        }
+        PhysicalPlanType::Extension(extension) => {
+            let (inputs, unbounded) = extension
+                .inputs
+                .iter()
+                .map(|i| parse_physical_plan(i, runtime, extension_codec))
+                .collect::<Result<Vec<_>, BallistaError>>()?
+                .into_iter()
+                .fold((Vec::new(), false), |(mut inputs, unbounded), (input, input_unbounded)| {
+                    inputs.push(input);
+                    (inputs, unbounded || input_unbounded)
+                });
+            Ok((
+                extension_codec.try_decode(&extension.node, &inputs)?,
+                unbounded,
+            ))
         }
     }
 }
@@ -283,3 +522,329 @@ fn compile_expr(
         .create_physical_expr(&expr, schema, &state)
         .map_err(|e| BallistaError::General(format!("{:?}", e)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn test_schema() -> protobuf::Schema {
+        protobuf::Schema {
+            columns: vec![protobuf::Field {
+                name: "a".to_string(),
+                arrow_type: protobuf::ArrowType::Int64 as i32,
+                nullable: false,
+                children: vec![],
+            }],
+        }
+    }
+
+    fn empty_node() -> protobuf::PhysicalPlanNode {
+        protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::Empty(protobuf::EmptyExecNode {
+                produce_one_row: false,
+                schema: Some(test_schema()),
+            })),
+        }
+    }
+
+    fn csv_scan_node(paths: Vec<String>, infinite_source: bool) -> protobuf::PhysicalPlanNode {
+        protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::Scan(protobuf::FileScanExecConfig {
+                path: paths,
+                projection: vec![],
+                batch_size: 8192,
+                target_partitions: 1,
+                infinite_source,
+                schema: Some(test_schema()),
+                file_format: Some(protobuf::file_scan_exec_config::FileFormat::Csv(
+                    protobuf::CsvScanOptions {
+                        has_header: true,
+                        file_extension: ".csv".to_string(),
+                        delimiter: ",".to_string(),
+                    },
+                )),
+            })),
+        }
+    }
+
+    /// Writes a tiny, valid CSV file to a unique path under the OS temp dir and returns that
+    /// path. Used by tests that need a real `CsvExec` rather than just exercising the error
+    /// paths, which don't need the file to exist.
+    fn write_temp_csv(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("ballista-from-proto-test-{}.csv", name));
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "a").unwrap();
+        writeln!(file, "1").unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    fn runtime() -> Arc<RuntimeEnv> {
+        Arc::new(RuntimeEnv::default())
+    }
+
+    #[test]
+    fn repartition_round_robin_builds_a_repartition_exec() {
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::Repartition(
+                protobuf::RepartitionExecNode {
+                    input: Some(Box::new(empty_node())),
+                    partition_method: Some(
+                        protobuf::repartition_node::PartitionMethod::RoundRobin(4),
+                    ),
+                },
+            )),
+        };
+        let (plan, unbounded) =
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap();
+        assert_eq!(plan.output_partitioning().partition_count(), 4);
+        assert!(!unbounded);
+    }
+
+    #[test]
+    fn repartition_hash_builds_a_repartition_exec() {
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::Repartition(
+                protobuf::RepartitionExecNode {
+                    input: Some(Box::new(empty_node())),
+                    partition_method: Some(protobuf::repartition_node::PartitionMethod::Hash(
+                        protobuf::PhysicalHashRepartition {
+                            hash_expr: vec![],
+                            partition_count: 3,
+                        },
+                    )),
+                },
+            )),
+        };
+        let (plan, _unbounded) =
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap();
+        assert_eq!(plan.output_partitioning().partition_count(), 3);
+    }
+
+    #[test]
+    fn default_extension_codec_rejects_extension_nodes() {
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::Extension(protobuf::PhysicalExtensionNode {
+                node: vec![],
+                inputs: vec![],
+            })),
+        };
+        let err = parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+            .unwrap_err();
+        assert!(matches!(err, BallistaError::General(_)));
+    }
+
+    #[test]
+    fn custom_extension_codec_reconstructs_extension_nodes() {
+        struct EchoInputCodec;
+        impl PhysicalExtensionCodec for EchoInputCodec {
+            fn try_decode(
+                &self,
+                _buf: &[u8],
+                inputs: &[Arc<dyn ExecutionPlan>],
+            ) -> Result<Arc<dyn ExecutionPlan>, BallistaError> {
+                Ok(inputs[0].clone())
+            }
+        }
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::Extension(protobuf::PhysicalExtensionNode {
+                node: vec![1, 2, 3],
+                inputs: vec![empty_node()],
+            })),
+        };
+        let (plan, unbounded) =
+            parse_physical_plan(&node, &runtime(), &EchoInputCodec).unwrap();
+        assert_eq!(plan.schema(), empty_node_schema());
+        assert!(!unbounded);
+    }
+
+    fn empty_node_schema() -> Arc<Schema> {
+        let node = empty_node();
+        let (plan, _) =
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap();
+        plan.schema()
+    }
+
+    #[test]
+    fn csv_scan_with_no_paths_is_a_checked_error_not_a_panic() {
+        let node = csv_scan_node(vec![], false);
+        let err = parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+            .unwrap_err();
+        assert!(matches!(err, BallistaError::General(_)));
+    }
+
+    #[test]
+    fn csv_scan_with_multiple_paths_is_rejected_instead_of_silently_dropped() {
+        let node = csv_scan_node(vec!["a.csv".to_string(), "b.csv".to_string()], false);
+        let err = parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+            .unwrap_err();
+        assert!(matches!(err, BallistaError::General(_)));
+    }
+
+    #[test]
+    fn bounded_csv_scan_reports_bounded() {
+        let path = write_temp_csv("bounded");
+        let node = csv_scan_node(vec![path], false);
+        let (_plan, unbounded) =
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap();
+        assert!(!unbounded);
+    }
+
+    #[test]
+    fn sort_rejects_an_unbounded_input() {
+        let path = write_temp_csv("sort-unbounded");
+        let scan = csv_scan_node(vec![path], true);
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::Sort(protobuf::SortExecNode {
+                input: Some(Box::new(scan)),
+                expr: vec![],
+                memory_limit: 0,
+                spill_dir: String::new(),
+            })),
+        };
+        let err = parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+            .unwrap_err();
+        assert!(matches!(err, BallistaError::General(_)));
+    }
+
+    #[test]
+    fn limit_turns_an_unbounded_input_bounded() {
+        let path = write_temp_csv("limit-unbounded");
+        let scan = csv_scan_node(vec![path], true);
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::LocalLimit(protobuf::LocalLimitExecNode {
+                input: Some(Box::new(scan)),
+                limit: 10,
+            })),
+        };
+        let (_plan, unbounded) =
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap();
+        assert!(!unbounded);
+    }
+
+    #[test]
+    fn hash_join_rejects_an_unbounded_build_side() {
+        let path = write_temp_csv("hashjoin-build-unbounded");
+        let unbounded_scan = csv_scan_node(vec![path], true);
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::HashJoin(protobuf::HashJoinExecNode {
+                left: Some(Box::new(unbounded_scan)),
+                right: Some(Box::new(empty_node())),
+                on: vec![],
+                join_type: protobuf::JoinType::Inner as i32,
+            })),
+        };
+        let err = parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+            .unwrap_err();
+        assert!(matches!(err, BallistaError::General(_)));
+    }
+
+    #[test]
+    fn hash_join_allows_an_unbounded_probe_side() {
+        let path = write_temp_csv("hashjoin-probe-unbounded");
+        let unbounded_scan = csv_scan_node(vec![path], true);
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::HashJoin(protobuf::HashJoinExecNode {
+                left: Some(Box::new(empty_node())),
+                right: Some(Box::new(unbounded_scan)),
+                on: vec![],
+                join_type: protobuf::JoinType::Inner as i32,
+            })),
+        };
+        let (_plan, unbounded) =
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap();
+        assert!(unbounded);
+    }
+
+    #[test]
+    fn cross_join_rejects_an_unbounded_build_side() {
+        let path = write_temp_csv("crossjoin-build-unbounded");
+        let unbounded_scan = csv_scan_node(vec![path], true);
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::CrossJoin(protobuf::CrossJoinExecNode {
+                left: Some(Box::new(unbounded_scan)),
+                right: Some(Box::new(empty_node())),
+            })),
+        };
+        let err = parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+            .unwrap_err();
+        assert!(matches!(err, BallistaError::General(_)));
+    }
+
+    #[test]
+    fn hash_join_maps_full_semi_and_anti_join_types() {
+        for join_type in [
+            protobuf::JoinType::Full,
+            protobuf::JoinType::Leftsemi,
+            protobuf::JoinType::Rightsemi,
+            protobuf::JoinType::Leftanti,
+            protobuf::JoinType::Rightanti,
+        ] {
+            let node = protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::HashJoin(protobuf::HashJoinExecNode {
+                    left: Some(Box::new(empty_node())),
+                    right: Some(Box::new(empty_node())),
+                    on: vec![],
+                    join_type: join_type as i32,
+                })),
+            };
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap_or_else(|e| panic!("{:?} should deserialize, got {:?}", join_type, e));
+        }
+    }
+
+    #[test]
+    fn final_hash_aggregate_rejects_an_unbounded_input_even_when_grouped() {
+        let path = write_temp_csv("final-agg-unbounded");
+        let unbounded_scan = csv_scan_node(vec![path], true);
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::HashAggregate(
+                protobuf::HashAggregateExecNode {
+                    input: Some(Box::new(unbounded_scan)),
+                    mode: protobuf::AggregateMode::Final as i32,
+                    group_expr: vec![protobuf::LogicalExprNode {
+                        expr_type: Some(ExprType::Column(protobuf::Column {
+                            name: "a".to_string(),
+                            relation: None,
+                        })),
+                    }],
+                    aggr_expr: vec![],
+                },
+            )),
+        };
+        let err = parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+            .unwrap_err();
+        assert!(matches!(err, BallistaError::General(_)));
+    }
+
+    #[test]
+    fn partial_hash_aggregate_allows_an_unbounded_input() {
+        let path = write_temp_csv("partial-agg-unbounded");
+        let unbounded_scan = csv_scan_node(vec![path], true);
+        let node = protobuf::PhysicalPlanNode {
+            physical_plan_type: Some(PhysicalPlanType::HashAggregate(
+                protobuf::HashAggregateExecNode {
+                    input: Some(Box::new(unbounded_scan)),
+                    mode: protobuf::AggregateMode::Partial as i32,
+                    group_expr: vec![protobuf::LogicalExprNode {
+                        expr_type: Some(ExprType::Column(protobuf::Column {
+                            name: "a".to_string(),
+                            relation: None,
+                        })),
+                    }],
+                    aggr_expr: vec![],
+                },
+            )),
+        };
+        let (_plan, unbounded) =
+            parse_physical_plan(&node, &runtime(), &DefaultPhysicalExtensionCodec::default())
+                .unwrap();
+        assert!(unbounded);
+    }
+}